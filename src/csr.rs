@@ -0,0 +1,186 @@
+/// Immutable CSR (compressed-sparse-row) snapshot of a `Graph`, trading
+/// `HashMap<u32, Transaction>` lookups for two parallel arrays so repeated
+/// parent lookups during closure/dominator/traversal passes hit contiguous
+/// memory instead of bouncing through hash buckets. Read-only; rebuilt on
+/// demand via `Graph::to_csr`, leaving the mutable `Graph` as the ingest
+/// structure.
+use std::collections::HashMap;
+
+use crate::dominators::DominatorTree;
+use crate::domain::TransactionMetrics;
+use crate::graph::Graph;
+use crate::reachability::Reachability;
+use crate::tips;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrGraph {
+    id_of: Vec<u32>,
+    index_of: HashMap<u32, usize>,
+    row_offsets: Vec<u32>,
+    targets: Vec<u32>,
+    metrics: Vec<TransactionMetrics>,
+}
+
+impl CsrGraph {
+    /// Builds a dense-id-order snapshot of `graph`. Nodes are stored in
+    /// increasing id order; neighbors of dense index `i` are
+    /// `targets[row_offsets[i]..row_offsets[i+1]]`.
+    pub(crate) fn build(graph: &Graph) -> Self {
+        let mut id_of: Vec<u32> = graph.nodes.keys().copied().collect();
+        id_of.sort_unstable();
+        let index_of: HashMap<u32, usize> = id_of
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+
+        let mut row_offsets = Vec::with_capacity(id_of.len() + 1);
+        let mut targets = Vec::new();
+        let mut metrics = Vec::with_capacity(id_of.len());
+        row_offsets.push(0u32);
+        for &id in &id_of {
+            let node = &graph.nodes[&id];
+            if let Some(parents) = node.parents {
+                targets.push(index_of[&parents.0] as u32);
+                targets.push(index_of[&parents.1] as u32);
+            }
+            row_offsets.push(targets.len() as u32);
+            metrics.push(node.metrics.clone());
+        }
+
+        CsrGraph {
+            id_of,
+            index_of,
+            row_offsets,
+            targets,
+            metrics,
+        }
+    }
+
+    /// Number of nodes in the snapshot.
+    pub fn len(&self) -> usize {
+        self.id_of.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id_of.is_empty()
+    }
+
+    /// Parent-edge targets of the node at dense index `i` (empty for the
+    /// ROOT, two entries otherwise), as dense indices into this snapshot.
+    pub fn neighbors(&self, i: usize) -> &[u32] {
+        let start = self.row_offsets[i] as usize;
+        let end = self.row_offsets[i + 1] as usize;
+        &self.targets[start..end]
+    }
+
+    /// Maps an original transaction id to its dense index, if present.
+    pub fn index_of(&self, id: u32) -> Option<usize> {
+        self.index_of.get(&id).copied()
+    }
+
+    /// Maps a dense index back to the original transaction id.
+    pub fn id_at(&self, index: usize) -> u32 {
+        self.id_of[index]
+    }
+
+    /// Metrics of the node at dense index `index`.
+    pub fn metrics_at(&self, index: usize) -> &TransactionMetrics {
+        &self.metrics[index]
+    }
+
+    /// Inverts the parent-edge (child -> parents) `targets` arrays into a
+    /// forward (parent -> children) index, dense index to dense indices.
+    /// The CSR-side equivalent of `Graph::children_of`, needed by the
+    /// analysis passes that walk away from the ROOT (dominators, tip
+    /// selection) against this snapshot.
+    pub fn children(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![Vec::new(); self.len()];
+        for i in 0..self.len() {
+            for &parent in self.neighbors(i) {
+                children[parent as usize].push(i);
+            }
+        }
+        // A node whose two parents are the same dense index (`neighbors(i)
+        // == [p, p]`) would otherwise be pushed into `children[p]` twice.
+        for ids in &mut children {
+            ids.sort_unstable();
+            ids.dedup();
+        }
+        children
+    }
+
+    /// Computes the transitive reachability closure directly from this
+    /// snapshot. See `Reachability::build_from_csr`.
+    pub fn reachability(&self) -> Reachability {
+        Reachability::build_from_csr(self)
+    }
+
+    /// Computes the immediate-dominator tree directly from this snapshot.
+    /// See `DominatorTree::build_from_csr`.
+    pub fn dominators(&self) -> DominatorTree {
+        DominatorTree::build_from_csr(self)
+    }
+
+    /// Picks two parents for a new transaction directly from this snapshot,
+    /// via the same MCMC random walk as `Graph::select_tips`.
+    pub fn select_tips(&self, alpha: f64) -> (u32, u32) {
+        tips::select_tips_from_csr(self, alpha, &mut rand::thread_rng())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Transaction;
+
+    #[test]
+    fn should_snapshot_nodes_in_dense_id_order() {
+        let mut graph = Graph::with_capacity(2);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 2, 2)).unwrap();
+        let csr = CsrGraph::build(&graph);
+
+        assert_eq!(3, csr.len());
+        assert_eq!(Some(0), csr.index_of(1));
+        assert_eq!(Some(1), csr.index_of(2));
+        assert_eq!(Some(2), csr.index_of(3));
+    }
+
+    #[test]
+    fn should_expose_parent_edges_as_contiguous_neighbor_slices() {
+        let mut graph = Graph::with_capacity(2);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 2, 2)).unwrap();
+        let csr = CsrGraph::build(&graph);
+
+        assert!(csr.neighbors(csr.index_of(1).unwrap()).is_empty());
+        assert_eq!(&[0u32, 0u32], csr.neighbors(csr.index_of(2).unwrap()));
+        assert_eq!(&[0u32, 1u32], csr.neighbors(csr.index_of(3).unwrap()));
+    }
+
+    #[test]
+    fn should_invert_parent_edges_into_a_forward_children_index() {
+        let mut graph = Graph::with_capacity(2);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 2, 2)).unwrap();
+        let csr = CsrGraph::build(&graph);
+
+        let children = csr.children();
+        assert_eq!(
+            vec![csr.index_of(2).unwrap(), csr.index_of(3).unwrap()],
+            children[csr.index_of(1).unwrap()]
+        );
+        assert_eq!(vec![csr.index_of(3).unwrap()], children[csr.index_of(2).unwrap()]);
+    }
+
+    #[test]
+    fn should_not_list_a_child_twice_when_both_its_parents_are_the_same_node() {
+        let mut graph = Graph::with_capacity(1);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        let csr = CsrGraph::build(&graph);
+
+        let children = csr.children();
+        assert_eq!(vec![csr.index_of(2).unwrap()], children[csr.index_of(1).unwrap()]);
+    }
+}
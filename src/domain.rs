@@ -3,20 +3,20 @@ use std::num::ParseIntError;
 
 // Domain classes
 #[derive(Debug, Clone, PartialEq)]
-pub struct Node {
+pub struct Transaction {
     pub id: u32,
     pub timestamp: u32,
     pub parents: Option<(u32, u32)>,
-    pub metrics: Metrics,
+    pub metrics: TransactionMetrics,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Metrics {
+pub struct TransactionMetrics {
     pub depth: u32,
     pub in_reference: u32,
 }
 
-impl fmt::Display for Metrics {
+impl fmt::Display for TransactionMetrics {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let output = format!(
             "(depth={:},in_reference={:})",
@@ -26,13 +26,13 @@ impl fmt::Display for Metrics {
     }
 }
 
-impl Node {
+impl Transaction {
     pub fn new(id: u32, left_parent: u32, right_parent: u32, timestamp: u32) -> Self {
-        Node {
+        Transaction {
             id,
             timestamp,
             parents: Some((left_parent, right_parent)),
-            metrics: Metrics {
+            metrics: TransactionMetrics {
                 depth: 0,
                 in_reference: 0,
             },
@@ -40,7 +40,7 @@ impl Node {
     }
 }
 
-impl TryFrom<(&[&str; 3], u32)> for Node {
+impl TryFrom<(&[&str; 3], u32)> for Transaction {
     type Error = ParseIntError;
     fn try_from(params: (&[&str; 3], u32)) -> Result<Self, ParseIntError> {
         let fields = params.0;
@@ -48,11 +48,16 @@ impl TryFrom<(&[&str; 3], u32)> for Node {
         let left_parent = fields[0].parse()?;
         let right_parent = fields[1].parse()?;
         let timestamp = fields[2].parse()?;
-        Ok(Node::new(id as u32, left_parent, right_parent, timestamp))
+        Ok(Transaction::new(
+            id as u32,
+            left_parent,
+            right_parent,
+            timestamp,
+        ))
     }
 }
 
-impl fmt::Display for Node {
+impl fmt::Display for Transaction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut output = String::new();
         if let Some(parents) = self.parents {
@@ -70,4 +75,23 @@ impl fmt::Display for Node {
         }
         write!(f, "{}", output)
     }
-}
\ No newline at end of file
+}
+
+/// Graph-wide metrics that are updated as transactions are added: the
+/// transaction with the latest timestamp (the earlier-added one on a tie)
+/// and the transaction with the highest `in_reference` count seen so far.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GeneralMetrics {
+    pub last_transaction: u32,
+    pub most_in_reference_transaction: u32,
+}
+
+impl fmt::Display for GeneralMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let output = format!(
+            "(last_transaction={:},most_in_reference_transaction={:})",
+            self.last_transaction, self.most_in_reference_transaction
+        );
+        write!(f, "{}", output)
+    }
+}
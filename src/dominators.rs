@@ -0,0 +1,229 @@
+/// Immediate-dominator tree over the confirmation DAG, computed with the
+/// iterative Cooper-Harvey-Kennedy algorithm rooted at the ROOT (id 1): the
+/// dominator of a node is the single transaction through which every path
+/// back to the ROOT must pass (its confirmation/merge point).
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::csr::CsrGraph;
+use crate::graph::Graph;
+
+/// Immediate-dominator relation computed by [`DominatorTree::build`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DominatorTree {
+    idom: HashMap<u32, u32>,
+}
+
+enum Frame<T> {
+    Enter(T),
+    Exit(T),
+}
+
+/// DFS postorder over the forward (parent -> child) edge set, starting at
+/// `root`. Generic over the node-identity type `T` so the same walk serves
+/// both `Graph` (`u32` ids, via `children_of`) and `CsrGraph` (`usize` dense
+/// indices, via its inverted `children()` index).
+fn postorder_dfs<T, F>(root: T, mut children_of: F) -> Vec<T>
+where
+    T: Copy + Eq + Hash,
+    F: FnMut(T) -> Vec<T>,
+{
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![Frame::Enter(root)];
+    while let Some(frame) = stack.pop() {
+        match frame {
+            Frame::Enter(id) => {
+                if !visited.insert(id) {
+                    continue;
+                }
+                stack.push(Frame::Exit(id));
+                for child in children_of(id).into_iter().rev() {
+                    if !visited.contains(&child) {
+                        stack.push(Frame::Enter(child));
+                    }
+                }
+            }
+            Frame::Exit(id) => order.push(id),
+        }
+    }
+    order
+}
+
+fn intersect<T>(mut a: T, mut b: T, idom: &HashMap<T, T>, postorder_number: &HashMap<T, usize>) -> T
+where
+    T: Copy + Eq + Hash,
+{
+    while a != b {
+        while postorder_number[&a] < postorder_number[&b] {
+            a = idom[&a];
+        }
+        while postorder_number[&b] < postorder_number[&a] {
+            b = idom[&b];
+        }
+    }
+    a
+}
+
+/// Iterative fixpoint solve of the Cooper-Harvey-Kennedy dataflow equations
+/// over reverse-postorder `rpo` (`rpo[0]` is `root`). Generic over the
+/// node-identity type `T` so `DominatorTree::build` and `build_from_csr`
+/// share one implementation.
+fn solve_idom<T, P>(root: T, rpo: &[T], postorder_number: &HashMap<T, usize>, mut predecessors_of: P) -> HashMap<T, T>
+where
+    T: Copy + Eq + Hash,
+    P: FnMut(T) -> Vec<T>,
+{
+    let mut idom: HashMap<T, T> = HashMap::new();
+    idom.insert(root, root);
+
+    loop {
+        let mut changed = false;
+        for &b in rpo.iter().skip(1) {
+            let mut new_idom: Option<T> = None;
+            for p in predecessors_of(b) {
+                if !idom.contains_key(&p) {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => p,
+                    Some(cur) => intersect(cur, p, &idom, postorder_number),
+                });
+            }
+            let new_idom =
+                new_idom.expect("every non-root node has a processed predecessor in RPO order");
+            if idom.get(&b) != Some(&new_idom) {
+                idom.insert(b, new_idom);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    idom
+}
+
+impl DominatorTree {
+    /// Builds the immediate-dominator tree for `graph`.
+    pub fn build(graph: &Graph) -> Self {
+        const ROOT: u32 = 1;
+        let postorder = postorder_dfs(ROOT, |id| graph.children_of(id).to_vec());
+        let postorder_number: HashMap<u32, usize> = postorder
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+        let rpo: Vec<u32> = postorder.into_iter().rev().collect();
+
+        let idom = solve_idom(ROOT, &rpo, &postorder_number, |b| {
+            let parents = graph.nodes[&b]
+                .parents
+                .expect("non-root transactions always have two parents");
+            vec![parents.0, parents.1]
+        });
+
+        DominatorTree { idom }
+    }
+
+    /// Builds the same immediate-dominator tree as `build`, but walks a
+    /// `CsrGraph`'s contiguous `targets` arrays (via its inverted `children`
+    /// index) instead of the `HashMap`-backed `Graph` -- the large-graph
+    /// speedup `Graph::to_csr` exists for.
+    pub fn build_from_csr(csr: &CsrGraph) -> Self {
+        let root = csr.index_of(1).expect("ROOT (id 1) must be present");
+        let children = csr.children();
+        let postorder = postorder_dfs(root, |i| children[i].clone());
+        let postorder_number: HashMap<usize, usize> = postorder
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| (idx, i))
+            .collect();
+        let rpo: Vec<usize> = postorder.into_iter().rev().collect();
+
+        let idom = solve_idom(root, &rpo, &postorder_number, |b| {
+            csr.neighbors(b).iter().map(|&p| p as usize).collect()
+        });
+        let idom = idom
+            .into_iter()
+            .map(|(node, dom)| (csr.id_at(node), csr.id_at(dom)))
+            .collect();
+
+        DominatorTree { idom }
+    }
+
+    /// Returns the immediate dominator of `id`, or `None` if `id` is not in
+    /// the graph.
+    pub fn idom(&self, id: u32) -> Option<u32> {
+        self.idom.get(&id).copied()
+    }
+
+    /// Returns true if `a` dominates `b`, i.e. every path from `b` back to
+    /// the ROOT passes through `a` (a node always dominates itself).
+    pub fn dominates(&self, a: u32, b: u32) -> bool {
+        if !self.idom.contains_key(&b) {
+            return false;
+        }
+        let mut current = b;
+        loop {
+            if current == a {
+                return true;
+            }
+            let parent = self.idom[&current];
+            if parent == current {
+                return false;
+            }
+            current = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Transaction;
+
+    fn diamond_graph() -> Graph {
+        // ROOT(1) -> 2 -> 4
+        //        \-> 3 -/
+        let mut graph = Graph::with_capacity(3);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        graph.add_node(&mut Transaction::new(4, 2, 3, 3)).unwrap();
+        graph
+    }
+
+    #[test]
+    fn should_make_root_its_own_idom() {
+        let tree = DominatorTree::build(&diamond_graph());
+        assert_eq!(Some(1), tree.idom(1));
+    }
+
+    #[test]
+    fn should_make_root_the_merge_point_of_a_diamond() {
+        let tree = DominatorTree::build(&diamond_graph());
+        assert_eq!(Some(1), tree.idom(4));
+        assert!(tree.dominates(1, 4));
+        assert!(!tree.dominates(2, 4));
+        assert!(!tree.dominates(3, 4));
+    }
+
+    #[test]
+    fn should_dominate_its_own_descendants_on_a_single_chain() {
+        let mut graph = Graph::with_capacity(1);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        let tree = DominatorTree::build(&graph);
+        assert_eq!(Some(1), tree.idom(2));
+        assert!(tree.dominates(1, 2));
+        assert!(tree.dominates(2, 2));
+    }
+
+    #[test]
+    fn should_match_hashmap_tree_when_built_from_csr() {
+        let graph = diamond_graph();
+        let from_graph = DominatorTree::build(&graph);
+        let from_csr = DominatorTree::build_from_csr(&graph.to_csr());
+        assert_eq!(from_graph, from_csr);
+    }
+}
@@ -6,7 +6,12 @@ use std::fmt;
 
 use thiserror::Error;
 
+use crate::csr::CsrGraph;
+use crate::dominators::DominatorTree;
 use crate::domain::{GeneralMetrics, Transaction, TransactionMetrics};
+use crate::reachability::Reachability;
+use crate::scope::Scope;
+use crate::traversal::{BfsAncestors, BfsDescendants, DfsAncestors, DfsDescendants};
 
 /// Type errors from the Graph structure
 #[derive(Error, Debug, PartialEq)]
@@ -17,6 +22,12 @@ pub enum GraphError {
     ParentNotFound,
     #[error("not specified parent")]
     ParentNotSpecified,
+    #[error("depth `{0}` exceeds the scope's maximum allowed depth")]
+    DepthExceeded(u32),
+    #[error("capacity exceeded: scope allows at most `{0}` non-root nodes")]
+    CapacityExceeded(u32),
+    #[error("timestamp `{0}` is earlier than a parent's timestamp")]
+    NonMonotonicTimestamp(u32),
 }
 
 /// Graph structure, it includes the counter of nodes and all the loaded
@@ -27,6 +38,12 @@ pub struct Graph {
     pub num_nodes: u32,
     pub nodes: HashMap<u32, Transaction>,
     pub metrics: GeneralMetrics,
+    /// Reverse index of `parents`, caching each node's referencing children
+    /// so descendant traversals don't have to scan `nodes` to invert it.
+    children: HashMap<u32, Vec<u32>>,
+    /// Constraints a candidate transaction must satisfy to be admitted;
+    /// unconstrained by default.
+    scope: Scope,
 }
 /// Representation of a ROOT transaction with id 1
 const ROOT_NODE: Transaction = Transaction {
@@ -49,6 +66,17 @@ impl Graph {
             num_nodes,
             nodes,
             metrics: Default::default(),
+            children: HashMap::new(),
+            scope: Scope::unconstrained(),
+        }
+    }
+
+    /// Constructor like `with_capacity`, additionally attaching a `Scope`
+    /// that `add_node` consults before admitting a candidate transaction.
+    pub fn with_scope(num_child: u32, scope: Scope) -> Self {
+        Graph {
+            scope,
+            ..Self::with_capacity(num_child)
         }
     }
 
@@ -81,6 +109,8 @@ impl Graph {
             return Err(GraphError::ParentNotFound);
         }
 
+        self.check_scope(node, parents)?;
+
         /* setting metrics */
         self.update_metrics(node);
 
@@ -88,6 +118,36 @@ impl Graph {
         self.add_vertex(node);
         Ok(())
     }
+
+    /// Validates `node` against the attached `Scope`, without mutating the
+    /// graph, so a rejected candidate never leaves partial metric updates
+    /// behind.
+    fn check_scope(&self, node: &Transaction, parents: (u32, u32)) -> Result<(), GraphError> {
+        let left = &self.nodes[&parents.0];
+        let right = &self.nodes[&parents.1];
+
+        if let Some(max_nodes) = self.scope.max_nodes {
+            let non_root_nodes = self.nodes.len() as u32 - 1;
+            if non_root_nodes >= max_nodes {
+                return Err(GraphError::CapacityExceeded(max_nodes));
+            }
+        }
+
+        if let Some(max_depth) = self.scope.max_depth {
+            let depth = std::cmp::min(left.metrics.depth, right.metrics.depth) + 1;
+            if depth > max_depth {
+                return Err(GraphError::DepthExceeded(depth));
+            }
+        }
+
+        if self.scope.monotonic_timestamps
+            && (node.timestamp < left.timestamp || node.timestamp < right.timestamp)
+        {
+            return Err(GraphError::NonMonotonicTimestamp(node.timestamp));
+        }
+
+        Ok(())
+    }
     fn update_metrics(&mut self, node: &mut Transaction) {
         /* Update parent nodes */
         let left_parent = self
@@ -116,6 +176,65 @@ impl Graph {
         self.update_last_transaction(node);
         self.update_most_in_reference_transaction(left_parent_metrics);
         self.update_most_in_reference_transaction(right_parent_metrics);
+
+        /* caching the reverse (child) adjacency for descendant traversals */
+        let parents = node.parents.unwrap();
+        self.children.entry(parents.0).or_default().push(node.id);
+        self.children.entry(parents.1).or_default().push(node.id);
+    }
+
+    /// Returns the ids of the transactions that directly reference `id` as a
+    /// parent, used by the descendant traversal iterators.
+    pub(crate) fn children_of(&self, id: u32) -> &[u32] {
+        self.children.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Computes the transitive reachability closure over the current set of
+    /// parent edges, answering "does transaction A directly or indirectly
+    /// reference B?" queries.
+    pub fn reachability(&self) -> Reachability {
+        Reachability::build(self)
+    }
+
+    /// Picks two parents for a new transaction via an MCMC random walk from
+    /// the ROOT, biased toward children with higher cumulative weight
+    /// (`alpha` controls how strongly; `alpha = 0` is a uniform walk).
+    pub fn select_tips(&self, alpha: f64) -> (u32, u32) {
+        crate::tips::select_tips(self, alpha, &mut rand::thread_rng())
+    }
+
+    /// Computes the immediate-dominator tree of the confirmation DAG,
+    /// identifying for every node the single transaction through which all
+    /// paths back to the ROOT must pass.
+    pub fn dominators(&self) -> DominatorTree {
+        DominatorTree::build(self)
+    }
+
+    /// Breadth-first walk from `id` toward the ROOT along parent edges.
+    pub fn bfs_ancestors(&self, id: u32) -> BfsAncestors<'_> {
+        BfsAncestors::new(self, id)
+    }
+
+    /// Depth-first walk from `id` toward the ROOT along parent edges.
+    pub fn dfs_ancestors(&self, id: u32) -> DfsAncestors<'_> {
+        DfsAncestors::new(self, id)
+    }
+
+    /// Breadth-first walk from `id` away from the ROOT along child edges.
+    pub fn bfs_descendants(&self, id: u32) -> BfsDescendants<'_> {
+        BfsDescendants::new(self, id)
+    }
+
+    /// Depth-first walk from `id` away from the ROOT along child edges.
+    pub fn dfs_descendants(&self, id: u32) -> DfsDescendants<'_> {
+        DfsDescendants::new(self, id)
+    }
+
+    /// Builds an immutable CSR snapshot of the current nodes, trading the
+    /// `HashMap` lookups used for ingest for contiguous edge arrays better
+    /// suited to repeated analysis passes over large graphs.
+    pub fn to_csr(&self) -> CsrGraph {
+        CsrGraph::build(self)
     }
 
     fn update_last_transaction(&mut self, node: &Transaction) {
@@ -227,4 +346,48 @@ mod tests {
         assert!(graph.add_node(&mut node.clone()).is_ok());
         assert!(graph.add_node(&mut node.clone()).is_err());
     }
+
+    #[test]
+    fn should_reject_a_node_exceeding_the_scope_s_max_depth() {
+        let scope = Scope {
+            max_depth: Some(0),
+            ..Scope::default()
+        };
+        let mut graph = Graph::with_scope(1, scope);
+        let mut node = Transaction::new(2, 1, 1, 0);
+        assert_eq!(
+            Err(GraphError::DepthExceeded(1)),
+            graph.add_node(&mut node)
+        );
+    }
+
+    #[test]
+    fn should_reject_a_node_once_the_scope_s_capacity_is_reached() {
+        let scope = Scope {
+            max_nodes: Some(0),
+            ..Scope::default()
+        };
+        let mut graph = Graph::with_scope(1, scope);
+        let mut node = Transaction::new(2, 1, 1, 0);
+        assert_eq!(
+            Err(GraphError::CapacityExceeded(0)),
+            graph.add_node(&mut node)
+        );
+    }
+
+    #[test]
+    fn should_reject_a_node_timestamped_before_its_parent() {
+        let scope = Scope {
+            monotonic_timestamps: true,
+            ..Scope::default()
+        };
+        let mut graph = Graph::with_scope(1, scope);
+        let mut node = Transaction::new(2, 1, 1, 0);
+        node.timestamp = 0;
+        graph.nodes.get_mut(&1).unwrap().timestamp = 5;
+        assert_eq!(
+            Err(GraphError::NonMonotonicTimestamp(0)),
+            graph.add_node(&mut node)
+        );
+    }
 }
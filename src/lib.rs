@@ -0,0 +1,9 @@
+pub mod csr;
+pub mod dominators;
+pub mod domain;
+pub mod export;
+pub mod graph;
+pub mod reachability;
+pub mod scope;
+mod tips;
+pub mod traversal;
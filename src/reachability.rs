@@ -0,0 +1,215 @@
+/// Transitive reachability over the confirmation DAG, backed by a packed
+/// bit-matrix: row `i` has a 1-bit at column `j` iff the node at row `i`
+/// can reach the node at column `j` through one or more parent edges.
+use std::collections::HashMap;
+
+use crate::csr::CsrGraph;
+use crate::graph::Graph;
+
+/// Number of bits packed into each word of the bit-matrix.
+const WORD_BITS: usize = 64;
+
+/// Packed bit-matrix recording, for every node, the set of nodes it
+/// directly or indirectly references (its ancestors). Built once from a
+/// `Graph` snapshot via [`Reachability::build`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reachability {
+    index_of: HashMap<u32, usize>,
+    id_of: Vec<u32>,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl Reachability {
+    /// Computes the transitive closure of the parent-edge relation for
+    /// `graph`. Ids are mapped to dense row indices since ids are not
+    /// contiguous.
+    pub fn build(graph: &Graph) -> Self {
+        let mut ids: Vec<u32> = graph.nodes.keys().copied().collect();
+        ids.sort_unstable();
+        let index_of: HashMap<u32, usize> =
+            ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let words_per_row = ids.len().div_ceil(WORD_BITS).max(1);
+
+        let mut reach = Reachability {
+            index_of,
+            id_of: ids.clone(),
+            words_per_row,
+            bits: vec![0u64; ids.len() * words_per_row],
+        };
+
+        // Seed the matrix from each transaction's two parent edges.
+        for &id in &ids {
+            if let Some(parents) = graph.nodes[&id].parents {
+                let src = reach.index_of[&id];
+                reach.set(src, reach.index_of[&parents.0]);
+                reach.set(src, reach.index_of[&parents.1]);
+            }
+        }
+
+        // Process nodes in increasing-depth order, which settles most
+        // parents' rows before their children fold them in (depth follows
+        // the shallower parent, so a deeper parent can still come later),
+        // then keep sweeping until a full pass changes nothing (the
+        // transitive closure fixpoint).
+        let mut order = ids;
+        order.sort_by_key(|id| graph.nodes[id].metrics.depth);
+
+        loop {
+            let mut changed = false;
+            for &id in &order {
+                let Some(parents) = graph.nodes[&id].parents else {
+                    continue;
+                };
+                let dst = reach.index_of[&id];
+                for parent_id in [parents.0, parents.1] {
+                    let src = reach.index_of[&parent_id];
+                    changed |= reach.or_row_into(src, dst);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        reach
+    }
+
+    /// Computes the same transitive closure as `build`, but iterates a
+    /// `CsrGraph`'s contiguous `targets` arrays instead of walking the
+    /// `HashMap`-backed `Graph` -- the large-graph speedup `Graph::to_csr`
+    /// exists for.
+    pub fn build_from_csr(csr: &CsrGraph) -> Self {
+        let n = csr.len();
+        let words_per_row = n.div_ceil(WORD_BITS).max(1);
+        let id_of: Vec<u32> = (0..n).map(|i| csr.id_at(i)).collect();
+        let index_of: HashMap<u32, usize> =
+            id_of.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut reach = Reachability {
+            index_of,
+            id_of,
+            words_per_row,
+            bits: vec![0u64; n * words_per_row],
+        };
+
+        for src in 0..n {
+            for &tgt in csr.neighbors(src) {
+                reach.set(src, tgt as usize);
+            }
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| csr.metrics_at(i).depth);
+
+        loop {
+            let mut changed = false;
+            for &dst in &order {
+                for &src in csr.neighbors(dst) {
+                    changed |= reach.or_row_into(src as usize, dst);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        reach
+    }
+
+    fn set(&mut self, src: usize, tgt: usize) {
+        let word = tgt / WORD_BITS;
+        let mask = 1u64 << (tgt % WORD_BITS);
+        self.bits[src * self.words_per_row + word] |= mask;
+    }
+
+    fn contains(&self, src: usize, tgt: usize) -> bool {
+        let word = tgt / WORD_BITS;
+        let mask = 1u64 << (tgt % WORD_BITS);
+        self.bits[src * self.words_per_row + word] & mask != 0
+    }
+
+    /// ORs row `src` into row `dst`, returning whether any word changed.
+    fn or_row_into(&mut self, src: usize, dst: usize) -> bool {
+        let mut changed = false;
+        for word in 0..self.words_per_row {
+            let incoming = self.bits[src * self.words_per_row + word];
+            let slot = &mut self.bits[dst * self.words_per_row + word];
+            if incoming & !*slot != 0 {
+                *slot |= incoming;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Returns true if `id` can directly or indirectly reach `ancestor`
+    /// through parent edges (i.e. `id` references `ancestor`).
+    pub fn is_ancestor(&self, id: u32, ancestor: u32) -> bool {
+        let (Some(&src), Some(&tgt)) = (self.index_of.get(&id), self.index_of.get(&ancestor))
+        else {
+            return false;
+        };
+        self.contains(src, tgt)
+    }
+
+    /// Iterates the ancestors of `id` (transactions it directly or
+    /// indirectly references) in increasing id order.
+    pub fn ancestors(&self, id: u32) -> impl Iterator<Item = u32> + '_ {
+        let row = self.index_of.get(&id).copied();
+        (0..self.id_of.len())
+            .filter(move |&tgt| row.is_some_and(|src| self.contains(src, tgt)))
+            .map(move |tgt| self.id_of[tgt])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Transaction;
+
+    fn sample_graph() -> Graph {
+        // ROOT(1) <- 2 <- 4
+        //        \ <- 3 <-/
+        let mut graph = Graph::with_capacity(3);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        graph.add_node(&mut Transaction::new(4, 2, 3, 3)).unwrap();
+        graph
+    }
+
+    #[test]
+    fn should_mark_root_as_ancestor_of_every_reachable_node() {
+        let graph = sample_graph();
+        let reach = Reachability::build(&graph);
+        assert!(reach.is_ancestor(2, 1));
+        assert!(reach.is_ancestor(3, 1));
+        assert!(reach.is_ancestor(4, 1));
+    }
+
+    #[test]
+    fn should_find_indirect_ancestors_through_two_hops() {
+        let graph = sample_graph();
+        let reach = Reachability::build(&graph);
+        assert!(reach.is_ancestor(4, 2));
+        assert!(reach.is_ancestor(4, 3));
+        assert!(reach.is_ancestor(4, 1));
+        assert!(!reach.is_ancestor(1, 4));
+    }
+
+    #[test]
+    fn should_iterate_ancestors_in_increasing_id_order() {
+        let graph = sample_graph();
+        let reach = Reachability::build(&graph);
+        let ancestors: Vec<u32> = reach.ancestors(4).collect();
+        assert_eq!(vec![1, 2, 3], ancestors);
+    }
+
+    #[test]
+    fn should_match_hashmap_closure_when_built_from_csr() {
+        let graph = sample_graph();
+        let from_graph = Reachability::build(&graph);
+        let from_csr = Reachability::build_from_csr(&graph.to_csr());
+        assert_eq!(from_graph, from_csr);
+    }
+}
@@ -0,0 +1,22 @@
+/// Constraints a candidate transaction must satisfy before `Graph::add_node`
+/// admits it into the DAG: a maximum depth, a maximum node count, and a
+/// monotonicity rule on timestamps. Defaults to unconstrained so existing
+/// behavior is preserved unless a caller opts in via `Graph::with_scope`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Scope {
+    /// Maximum `depth` a candidate transaction may have, if bounded.
+    pub max_depth: Option<u32>,
+    /// Maximum number of non-root transactions the graph may hold, if
+    /// bounded.
+    pub max_nodes: Option<u32>,
+    /// When true, a candidate's `timestamp` must be >= each parent's
+    /// `timestamp`.
+    pub monotonic_timestamps: bool,
+}
+
+impl Scope {
+    /// A scope with no constraints, equivalent to `Scope::default()`.
+    pub fn unconstrained() -> Self {
+        Self::default()
+    }
+}
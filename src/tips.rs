@@ -0,0 +1,215 @@
+/// MCMC tip selection: picks the two parents a new transaction should
+/// attach to by walking from the ROOT toward its referencing children,
+/// biased toward the tangle's more heavily-confirmed side.
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::csr::CsrGraph;
+use crate::graph::Graph;
+
+/// Cumulative weight of every node: 1 plus the number of transactions that
+/// directly or indirectly reference it.
+fn cumulative_weights(graph: &Graph) -> HashMap<u32, u32> {
+    let reach = graph.reachability();
+    graph
+        .nodes
+        .keys()
+        .map(|&id| {
+            let referencing = graph
+                .nodes
+                .keys()
+                .filter(|&&other| other != id && reach.is_ancestor(other, id))
+                .count() as u32;
+            (id, 1 + referencing)
+        })
+        .collect()
+}
+
+/// Same cumulative weights as `cumulative_weights`, but over a `CsrGraph`'s
+/// dense indices. See `Reachability::build_from_csr`.
+fn cumulative_weights_from_csr(csr: &CsrGraph) -> Vec<u32> {
+    let reach = csr.reachability();
+    (0..csr.len())
+        .map(|i| {
+            let id = csr.id_at(i);
+            let referencing = (0..csr.len())
+                .filter(|&j| j != i && reach.is_ancestor(csr.id_at(j), id))
+                .count() as u32;
+            1 + referencing
+        })
+        .collect()
+}
+
+/// Runs a single weighted random walk from `start` toward its referencing
+/// children until it reaches a tip (a node with no children). At each node
+/// `x` the walk moves to child `y` with probability proportional to
+/// `exp(-alpha * (CW(x) - CW(y)))`; candidates are visited in increasing
+/// order so exact weight ties are broken deterministically. Generic over
+/// the node-identity type `T` so the same walk serves both `Graph` (`u32`
+/// ids) and `CsrGraph` (`usize` dense indices).
+fn walk_to_tip<T, C, W>(start: T, mut children_of: C, mut weight_of: W, alpha: f64, rng: &mut impl Rng) -> T
+where
+    T: Copy + Ord,
+    C: FnMut(T) -> Vec<T>,
+    W: FnMut(T) -> f64,
+{
+    let mut current = start;
+    loop {
+        let mut candidates = children_of(current);
+        if candidates.is_empty() {
+            return current;
+        }
+        // A node whose two parents are the same node (e.g. `parents ==
+        // (x, x)`) appears twice in x's child list; dedup so it gets one
+        // walk-to candidate and one share of the transition probability,
+        // not two.
+        candidates.sort();
+        candidates.dedup();
+
+        let cw_current = weight_of(current);
+        let scored: Vec<(T, f64)> = candidates
+            .iter()
+            .map(|&c| (c, (-alpha * (cw_current - weight_of(c))).exp()))
+            .collect();
+        let total: f64 = scored.iter().map(|(_, w)| w).sum();
+
+        // A large alpha or a wide cumulative-weight spread can underflow
+        // every candidate's weight to 0.0 (or overflow it to +inf, making
+        // the sum non-finite); sampling an empty `0.0..0.0` range would
+        // panic, so fall back to a uniform pick over the sorted candidates.
+        current = if total > 0.0 && total.is_finite() {
+            let mut pick = rng.gen_range(0.0..total);
+            scored
+                .iter()
+                .find(|(_, w)| {
+                    if pick < *w {
+                        true
+                    } else {
+                        pick -= w;
+                        false
+                    }
+                })
+                .map(|&(c, _)| c)
+                .unwrap_or(scored[0].0)
+        } else {
+            scored[rng.gen_range(0..scored.len())].0
+        };
+    }
+}
+
+/// Selects two tips for a new transaction to attach to, as described by
+/// `Graph::select_tips`.
+pub(crate) fn select_tips(graph: &Graph, alpha: f64, rng: &mut impl Rng) -> (u32, u32) {
+    let weights = cumulative_weights(graph);
+    let first = walk_to_tip(1u32, |id| graph.children_of(id).to_vec(), |id| weights[&id] as f64, alpha, rng);
+    let second = walk_to_tip(1u32, |id| graph.children_of(id).to_vec(), |id| weights[&id] as f64, alpha, rng);
+    (first, second)
+}
+
+/// Selects two tips directly from a `CsrGraph` snapshot, as described by
+/// `CsrGraph::select_tips`.
+pub(crate) fn select_tips_from_csr(csr: &CsrGraph, alpha: f64, rng: &mut impl Rng) -> (u32, u32) {
+    let root = csr.index_of(1).expect("ROOT (id 1) must be present");
+    let children = csr.children();
+    let weights = cumulative_weights_from_csr(csr);
+    let first = walk_to_tip(root, |i| children[i].clone(), |i| weights[i] as f64, alpha, rng);
+    let second = walk_to_tip(root, |i| children[i].clone(), |i| weights[i] as f64, alpha, rng);
+    (csr.id_at(first), csr.id_at(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Transaction;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn should_return_root_twice_when_graph_has_only_root() {
+        let graph = Graph::with_capacity(0);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!((1, 1), select_tips(&graph, 1.0, &mut rng));
+    }
+
+    #[test]
+    fn should_land_on_an_actual_tip() {
+        let mut graph = Graph::with_capacity(3);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        graph.add_node(&mut Transaction::new(4, 2, 3, 3)).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let (a, b) = select_tips(&graph, 0.5, &mut rng);
+        assert_eq!(0, graph.nodes[&a].metrics.in_reference);
+        assert_eq!(0, graph.nodes[&b].metrics.in_reference);
+    }
+
+    #[test]
+    fn should_reach_a_tip_when_alpha_is_zero() {
+        let mut graph = Graph::with_capacity(2);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let weights = cumulative_weights(&graph);
+        let tip = walk_to_tip(1u32, |id| graph.children_of(id).to_vec(), |id| weights[&id] as f64, 0.0, &mut rng);
+        assert!(tip == 2 || tip == 3);
+    }
+
+    #[test]
+    fn should_not_double_count_a_candidate_listed_twice() {
+        // A node whose child list contains the same id twice (as happens
+        // when a transaction references the same parent twice over, e.g.
+        // `Transaction::new(id, 1, 1, _)`) must still get a single,
+        // uniform share of the transition probability.
+        let mut rng = StdRng::seed_from_u64(11);
+        let trials = 2000;
+        let mut landed_on_3 = 0;
+        for _ in 0..trials {
+            let tip = walk_to_tip(
+                1u32,
+                |id| if id == 1 { vec![2, 2, 3] } else { vec![] },
+                |_| 1.0,
+                0.0,
+                &mut rng,
+            );
+            if tip == 3 {
+                landed_on_3 += 1;
+            }
+        }
+        let ratio = landed_on_3 as f64 / trials as f64;
+        assert!(
+            (0.4..0.6).contains(&ratio),
+            "expected a near-uniform split between deduped candidates, got {ratio}"
+        );
+    }
+
+    #[test]
+    fn should_not_panic_when_alpha_underflows_every_weight_to_zero() {
+        let mut graph = Graph::with_capacity(2);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let (a, b) = select_tips(&graph, 1000.0, &mut rng);
+        assert!(a == 2 || a == 3);
+        assert!(b == 2 || b == 3);
+    }
+
+    #[test]
+    fn should_return_root_twice_from_csr_when_graph_has_only_root() {
+        let graph = Graph::with_capacity(0);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!((1, 1), select_tips_from_csr(&graph.to_csr(), 1.0, &mut rng));
+    }
+
+    #[test]
+    fn should_land_on_an_actual_tip_from_csr() {
+        let mut graph = Graph::with_capacity(3);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        graph.add_node(&mut Transaction::new(4, 2, 3, 3)).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let (a, b) = select_tips_from_csr(&graph.to_csr(), 0.5, &mut rng);
+        assert_eq!(0, graph.nodes[&a].metrics.in_reference);
+        assert_eq!(0, graph.nodes[&b].metrics.in_reference);
+    }
+}
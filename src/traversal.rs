@@ -0,0 +1,197 @@
+/// Lazy traversal iterators over a `Graph`, walking either toward the ROOT
+/// (ancestors, via the two `parents` edges) or away from it (descendants,
+/// via the cached child adjacency index). Each iterator tracks visited ids
+/// so a node reachable through both parents is only emitted once.
+use std::collections::{HashSet, VecDeque};
+
+use crate::domain::Transaction;
+use crate::graph::Graph;
+
+/// Breadth-first walk from `start` toward the ROOT along parent edges.
+pub struct BfsAncestors<'g> {
+    graph: &'g Graph,
+    queue: VecDeque<u32>,
+    visited: HashSet<u32>,
+}
+
+/// Depth-first walk from `start` toward the ROOT along parent edges.
+pub struct DfsAncestors<'g> {
+    graph: &'g Graph,
+    stack: Vec<u32>,
+    visited: HashSet<u32>,
+}
+
+/// Breadth-first walk from `start` away from the ROOT along child edges.
+pub struct BfsDescendants<'g> {
+    graph: &'g Graph,
+    queue: VecDeque<u32>,
+    visited: HashSet<u32>,
+}
+
+/// Depth-first walk from `start` away from the ROOT along child edges.
+pub struct DfsDescendants<'g> {
+    graph: &'g Graph,
+    stack: Vec<u32>,
+    visited: HashSet<u32>,
+}
+
+fn seed(graph: &Graph, start: u32) -> (HashSet<u32>, Option<u32>) {
+    let mut visited = HashSet::new();
+    if graph.nodes.contains_key(&start) {
+        visited.insert(start);
+        (visited, Some(start))
+    } else {
+        (visited, None)
+    }
+}
+
+impl<'g> BfsAncestors<'g> {
+    pub(crate) fn new(graph: &'g Graph, start: u32) -> Self {
+        let (visited, seeded) = seed(graph, start);
+        let queue = seeded.into_iter().collect();
+        BfsAncestors {
+            graph,
+            queue,
+            visited,
+        }
+    }
+}
+
+impl<'g> Iterator for BfsAncestors<'g> {
+    type Item = &'g Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.queue.pop_front()?;
+        let node = &self.graph.nodes[&id];
+        if let Some(parents) = node.parents {
+            for parent in [parents.0, parents.1] {
+                if self.visited.insert(parent) {
+                    self.queue.push_back(parent);
+                }
+            }
+        }
+        Some(node)
+    }
+}
+
+impl<'g> DfsAncestors<'g> {
+    pub(crate) fn new(graph: &'g Graph, start: u32) -> Self {
+        let (visited, seeded) = seed(graph, start);
+        let stack = seeded.into_iter().collect();
+        DfsAncestors {
+            graph,
+            stack,
+            visited,
+        }
+    }
+}
+
+impl<'g> Iterator for DfsAncestors<'g> {
+    type Item = &'g Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.stack.pop()?;
+        let node = &self.graph.nodes[&id];
+        if let Some(parents) = node.parents {
+            for parent in [parents.0, parents.1] {
+                if self.visited.insert(parent) {
+                    self.stack.push(parent);
+                }
+            }
+        }
+        Some(node)
+    }
+}
+
+impl<'g> BfsDescendants<'g> {
+    pub(crate) fn new(graph: &'g Graph, start: u32) -> Self {
+        let (visited, seeded) = seed(graph, start);
+        let queue = seeded.into_iter().collect();
+        BfsDescendants {
+            graph,
+            queue,
+            visited,
+        }
+    }
+}
+
+impl<'g> Iterator for BfsDescendants<'g> {
+    type Item = &'g Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.queue.pop_front()?;
+        let node = &self.graph.nodes[&id];
+        for &child in self.graph.children_of(id) {
+            if self.visited.insert(child) {
+                self.queue.push_back(child);
+            }
+        }
+        Some(node)
+    }
+}
+
+impl<'g> DfsDescendants<'g> {
+    pub(crate) fn new(graph: &'g Graph, start: u32) -> Self {
+        let (visited, seeded) = seed(graph, start);
+        let stack = seeded.into_iter().collect();
+        DfsDescendants {
+            graph,
+            stack,
+            visited,
+        }
+    }
+}
+
+impl<'g> Iterator for DfsDescendants<'g> {
+    type Item = &'g Transaction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.stack.pop()?;
+        let node = &self.graph.nodes[&id];
+        for &child in self.graph.children_of(id) {
+            if self.visited.insert(child) {
+                self.stack.push(child);
+            }
+        }
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Transaction;
+
+    fn diamond_graph() -> Graph {
+        // ROOT(1) -> 2 -> 4
+        //        \-> 3 -/
+        let mut graph = Graph::with_capacity(3);
+        graph.add_node(&mut Transaction::new(2, 1, 1, 1)).unwrap();
+        graph.add_node(&mut Transaction::new(3, 1, 1, 2)).unwrap();
+        graph.add_node(&mut Transaction::new(4, 2, 3, 3)).unwrap();
+        graph
+    }
+
+    #[test]
+    fn should_visit_each_ancestor_once_through_both_parents() {
+        let graph = diamond_graph();
+        let mut ids: Vec<u32> = graph.bfs_ancestors(4).map(|t| t.id).collect();
+        ids.sort_unstable();
+        assert_eq!(vec![1, 2, 3, 4], ids);
+    }
+
+    #[test]
+    fn should_visit_each_descendant_once_via_dfs() {
+        let graph = diamond_graph();
+        let mut ids: Vec<u32> = graph.dfs_descendants(1).map(|t| t.id).collect();
+        ids.sort_unstable();
+        assert_eq!(vec![1, 2, 3, 4], ids);
+    }
+
+    #[test]
+    fn should_yield_nothing_for_an_unknown_start_node() {
+        let graph = diamond_graph();
+        assert_eq!(0, graph.bfs_ancestors(99).count());
+        assert_eq!(0, graph.dfs_descendants(99).count());
+    }
+}